@@ -1,4 +1,3 @@
-use merkle::hash;
 use merkle::MerkleTree;
 
 mod merkle;
@@ -6,7 +5,7 @@ mod merkle;
 fn main() {
     // create a tree
     let data: Vec<&[u8]> = vec![b"this"];
-    let mut merkle = MerkleTree::new(&data);
+    let mut merkle: MerkleTree = MerkleTree::new(&data);
 
     // add elements to the tree
     merkle.add(b"is");
@@ -18,6 +17,6 @@ fn main() {
     let proof = merkle.generate_proof(b"is").unwrap();
 
     // verify is a valid proof
-    let verified = merkle.verify(&proof, &hash(b"is"));
+    let verified = merkle.verify(&proof, b"is");
     println!("Can the proof be verified? {verified}");
 }
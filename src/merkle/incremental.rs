@@ -0,0 +1,330 @@
+use std::collections::HashMap;
+
+use super::{leaf_hash, node_hash, Hasher, MerklePath, MerkleRoot};
+
+/// Append-only Merkle tree of fixed `depth` that stores only the "frontier"
+/// (the rightmost filled node at each level) plus a running leaf count,
+/// instead of every hash of every level. `append` is O(depth) time and
+/// space regardless of how many leaves have been added; `root()` fills the
+/// as-yet-unused right subtrees with a precomputed empty-subtree hash per
+/// level.
+pub struct IncrementalMerkleTree<H: Hasher = super::Sha3_256Hasher> {
+    depth: usize,
+    tagged: bool,
+    /// frontier[height] is the left sibling still waiting for its pair at
+    /// that height, if any. The extra slot at index `depth` holds the root
+    /// once the tree has been filled to its full 2^depth capacity.
+    frontier: Vec<Option<H::Hash>>,
+    /// Hash of an empty subtree rooted at each height; `empty_hash[0]` is the empty leaf.
+    empty_hash: Vec<H::Hash>,
+    count: u64,
+    /// The authentication path collected so far for the most recently
+    /// appended leaf, so `witness` can capture it immediately afterwards.
+    last_append: Option<(usize, Vec<Option<H::Hash>>)>,
+    witnesses: HashMap<usize, Witness<H>>,
+}
+
+/// The authentication path for one leaf, kept up to date as later leaves
+/// are appended. Siblings only ever get filled in, never cleared.
+struct Witness<H: Hasher> {
+    index: usize,
+    /// Sibling per height, filled in as the corresponding subtree closes.
+    siblings: Vec<Option<H::Hash>>,
+}
+
+impl<H: Hasher> IncrementalMerkleTree<H> {
+    /// Builds an empty tree of the given depth using legacy, untagged hashing.
+    pub fn new(depth: usize) -> Self {
+        Self::build(depth, false)
+    }
+
+    /// Like `new`, but uses the domain-separated tagged hashing from `MerkleTree::new_tagged`.
+    pub fn new_tagged(depth: usize) -> Self {
+        Self::build(depth, true)
+    }
+
+    fn build(depth: usize, tagged: bool) -> Self {
+        let mut empty_hash = Vec::with_capacity(depth + 1);
+        empty_hash.push(leaf_hash::<H>(tagged, &[]));
+        for height in 0..depth {
+            let previous = empty_hash[height];
+            empty_hash.push(node_hash::<H>(tagged, &previous, &previous));
+        }
+
+        IncrementalMerkleTree {
+            depth,
+            tagged,
+            frontier: vec![None; depth + 1],
+            empty_hash,
+            count: 0,
+            last_append: None,
+            witnesses: HashMap::new(),
+        }
+    }
+
+    /// Number of leaves appended so far.
+    pub fn len(&self) -> u64 {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Appends a new leaf, updating the frontier in O(depth) and each
+    /// registered witness in O(depth), so O(depth × witnesses) in all.
+    pub fn append(&mut self, value: &[u8]) {
+        assert!(
+            self.count < (1u64 << self.depth),
+            "tree already holds the maximum 2^depth leaves"
+        );
+
+        let mut node = leaf_hash::<H>(self.tagged, value);
+        let index = self.count as usize;
+        let mut own_path = vec![None; self.depth];
+        let mut height = 0;
+
+        while height < self.depth && (index >> height) & 1 == 1 {
+            let left = self.frontier[height].take().expect(
+                "a set bit at this height means an earlier append stashed a left sibling",
+            );
+            own_path[height] = Some(left);
+
+            // `left` and `node` are the two halves of the pair closing at this
+            // height. A witness living in the left subtree still waiting on
+            // its sibling gets `node`; one living in the right subtree (and
+            // not the leaf being appended right now, whose path is captured
+            // separately below) gets `left`.
+            let append_ancestor = index >> height;
+            for witness in self.witnesses.values_mut() {
+                if witness.siblings[height].is_some() {
+                    continue;
+                }
+                let witness_ancestor = witness.index >> height;
+                if witness_ancestor + 1 == append_ancestor {
+                    witness.siblings[height] = Some(node);
+                } else if witness_ancestor == append_ancestor && witness.index != index {
+                    witness.siblings[height] = Some(left);
+                }
+            }
+
+            node = node_hash::<H>(self.tagged, &left, &node);
+            height += 1;
+        }
+
+        self.frontier[height] = Some(node);
+        self.last_append = Some((index, own_path));
+        self.count += 1;
+    }
+
+    /// The current root, treating not-yet-appended leaves as empty.
+    pub fn root(&self) -> H::Hash {
+        if self.count == 1u64 << self.depth {
+            return self.frontier[self.depth]
+                .expect("a tree filled to capacity stores its root in the extra frontier slot");
+        }
+
+        let mut node = self.empty_hash[0];
+        let mut size = self.count;
+
+        for height in 0..self.depth {
+            node = if size & 1 == 1 {
+                let left = self.frontier[height]
+                    .expect("a set bit at this height means it has a stashed left sibling");
+                node_hash::<H>(self.tagged, &left, &node)
+            } else {
+                node_hash::<H>(self.tagged, &node, &self.empty_hash[height])
+            };
+            size /= 2;
+        }
+
+        node
+    }
+
+    /// Starts (or restarts) tracking the authentication path for the leaf at
+    /// `index`, using the path collected while it was appended if `index`
+    /// was the most recently appended leaf, and leaving the rest for later
+    /// `append` calls to complete.
+    ///
+    /// Call this promptly: a sibling that closed before this call, and
+    /// wasn't the leaf's own append, cannot be recovered, since the tree
+    /// keeps no history beyond the frontier.
+    pub fn witness(&mut self, index: usize) {
+        let siblings = match &self.last_append {
+            Some((last_index, path)) if *last_index == index => path.clone(),
+            _ => vec![None; self.depth],
+        };
+        self.witnesses.insert(index, Witness { index, siblings });
+    }
+
+    /// Stops tracking the witness for `index`, freeing its O(depth) authentication path.
+    pub fn prune(&mut self, index: usize) {
+        self.witnesses.remove(&index);
+    }
+
+    /// The authentication path tracked for `index`, if a witness is
+    /// registered. A sibling subtree that is still entirely empty given the
+    /// current leaf count is filled in from the precomputed empty-subtree
+    /// hashes, the same way `root` pads the unused right-hand side; returns
+    /// `None` if a sibling is neither resolved nor provably empty yet.
+    pub fn authentication_path(&self, index: usize) -> Option<Vec<H::Hash>> {
+        let witness = self.witnesses.get(&index)?;
+        let mut path = Vec::with_capacity(self.depth);
+
+        for height in 0..self.depth {
+            if let Some(sibling) = witness.siblings[height] {
+                path.push(sibling);
+                continue;
+            }
+
+            let sibling_subtree = ((index >> height) ^ 1) as u64;
+            let sibling_start = sibling_subtree << height;
+            if self.count <= sibling_start {
+                path.push(self.empty_hash[height]);
+            } else {
+                return None;
+            }
+        }
+
+        Some(path)
+    }
+
+    /// Verifies that `value` is the leaf at `index`, using the witness
+    /// tracked for it and the current `root()` via the same sibling/index
+    /// logic as `MerkleRoot::check`.
+    pub fn verify_witness(&self, index: usize, value: &[u8]) -> bool {
+        match self.authentication_path(index) {
+            // Every level here is padded out to a power of two with
+            // `empty_hash`, so there's never a genuinely missing sibling;
+            // `usize::MAX` tells `check` to always expect one.
+            Some(path) => MerkleRoot::<H>::new(self.root(), self.tagged, usize::MAX)
+                .check(&MerklePath(path), value, index),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{MerkleTree, Sha3_256Hasher};
+    use super::*;
+
+    type TestTree = MerkleTree;
+
+    #[test]
+    fn root_matches_a_freshly_built_tree_once_padded_to_its_depth() {
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a", b"merkleTree"];
+        let merkle: TestTree = MerkleTree::new(&data);
+
+        // depth 3 gives the 4-leaf subtree room to be padded with one level
+        // of empty siblings, the same way the naive tree pads an odd level
+        // by duplicating its last node -- but via the precomputed empty hash.
+        let mut incremental: IncrementalMerkleTree = IncrementalMerkleTree::new(3);
+        for value in &data {
+            incremental.append(value);
+        }
+
+        let empty_leaf = Sha3_256Hasher::hash_leaf(&[]);
+        let empty_at_height_1 = Sha3_256Hasher::hash_nodes(&empty_leaf, &empty_leaf);
+        let empty_at_height_2 = Sha3_256Hasher::hash_nodes(&empty_at_height_1, &empty_at_height_1);
+        let padded_root =
+            Sha3_256Hasher::hash_nodes(merkle.get_root().unwrap(), &empty_at_height_2);
+        assert_eq!(incremental.root(), padded_root);
+    }
+
+    #[test]
+    fn root_matches_naive_tree_when_exactly_at_capacity() {
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a", b"merkleTree"];
+        let merkle: TestTree = MerkleTree::new(&data);
+
+        let mut incremental: IncrementalMerkleTree = IncrementalMerkleTree::new(2);
+        for value in &data {
+            incremental.append(value);
+        }
+
+        assert_eq!(incremental.root(), *merkle.get_root().unwrap());
+    }
+
+    #[test]
+    fn root_changes_as_leaves_are_appended() {
+        let mut incremental: IncrementalMerkleTree = IncrementalMerkleTree::new(4);
+        incremental.append(b"this");
+        let root_after_one = incremental.root();
+
+        incremental.append(b"is");
+        assert_ne!(root_after_one, incremental.root());
+    }
+
+    #[test]
+    fn witness_round_trips_for_a_pending_leaf() {
+        let mut incremental: IncrementalMerkleTree = IncrementalMerkleTree::new(4);
+        incremental.append(b"this");
+        incremental.witness(0);
+
+        for value in [b"is".as_slice(), b"a".as_slice(), b"merkleTree".as_slice()] {
+            incremental.append(value);
+        }
+
+        assert!(incremental.verify_witness(0, b"this"));
+    }
+
+    #[test]
+    fn witness_round_trips_for_a_leaf_with_an_existing_sibling() {
+        let mut incremental: IncrementalMerkleTree = IncrementalMerkleTree::new(4);
+        incremental.append(b"this");
+        incremental.append(b"is");
+        incremental.witness(1);
+
+        incremental.append(b"a");
+        incremental.append(b"merkleTree");
+
+        assert!(incremental.verify_witness(1, b"is"));
+    }
+
+    #[test]
+    fn witness_round_trips_for_a_leaf_whose_own_pair_closes_later() {
+        // index 2 is the left side of the {2, 3} pair and doesn't complete
+        // it during its own append; the witness has to be forward-filled
+        // when leaf 3 arrives and closes that pair.
+        let mut incremental: IncrementalMerkleTree = IncrementalMerkleTree::new(4);
+        incremental.append(b"this");
+        incremental.append(b"is");
+        incremental.append(b"a");
+        incremental.witness(2);
+
+        incremental.append(b"merkleTree");
+
+        assert!(incremental.verify_witness(2, b"a"));
+    }
+
+    #[test]
+    fn verify_witness_rejects_the_wrong_leaf() {
+        let mut incremental: IncrementalMerkleTree = IncrementalMerkleTree::new(4);
+        incremental.append(b"this");
+        incremental.witness(0);
+        incremental.append(b"is");
+
+        assert!(!incremental.verify_witness(0, b"not-this"));
+    }
+
+    #[test]
+    fn prune_drops_the_witness() {
+        let mut incremental: IncrementalMerkleTree = IncrementalMerkleTree::new(4);
+        incremental.append(b"this");
+        incremental.witness(0);
+        incremental.prune(0);
+        incremental.append(b"is");
+
+        assert!(incremental.authentication_path(0).is_none());
+    }
+
+    #[test]
+    fn tagged_root_differs_from_untagged() {
+        let mut untagged: IncrementalMerkleTree = IncrementalMerkleTree::new(4);
+        let mut tagged: IncrementalMerkleTree = IncrementalMerkleTree::new_tagged(4);
+        untagged.append(b"this");
+        tagged.append(b"this");
+
+        assert_ne!(untagged.root(), tagged.root());
+    }
+}
@@ -0,0 +1,721 @@
+use std::collections::BTreeSet;
+
+mod hasher;
+mod incremental;
+mod multiproof;
+mod proof;
+mod sparse;
+
+pub use hasher::{Hasher, Sha3_256Hasher, Sha512Hasher};
+pub use incremental::IncrementalMerkleTree;
+pub use multiproof::MultiProof;
+pub use proof::{MerklePath, MerkleRoot, Proof};
+pub use sparse::{SparseMerkleTree, SparseProof, Terminal};
+
+/// enum for errors related to the Merkle Tree
+/// NonExistingElement is for when generating a proof for an element it's not contained in the tree
+/// OutOfBounds is for when an operation is given a leaf index beyond the current leaf count
+/// EmptyInput is for when an operation that needs at least one value is given none
+#[derive(Debug)]
+pub enum MerkleError {
+    NonExistingElement,
+    OutOfBounds,
+    EmptyInput,
+}
+
+/// Hashes a leaf's raw bytes, applying the domain-separation tag when `tagged` is set.
+pub(crate) fn leaf_hash<H: Hasher>(tagged: bool, value: &[u8]) -> H::Hash {
+    if tagged {
+        H::hash_tagged_leaf(value)
+    } else {
+        H::hash_leaf(value)
+    }
+}
+
+/// Hashes two children into their parent, applying the domain-separation tag when `tagged` is set.
+pub(crate) fn node_hash<H: Hasher>(tagged: bool, left: &H::Hash, right: &H::Hash) -> H::Hash {
+    if tagged {
+        H::hash_tagged_nodes(left, right)
+    } else {
+        H::hash_nodes(left, right)
+    }
+}
+
+/// Hashes a node that has no sibling: the `0x02` tag when `tagged` is set,
+/// otherwise the legacy behaviour of hashing the node against itself.
+pub(crate) fn null_hash<H: Hasher>(tagged: bool, node: &H::Hash) -> H::Hash {
+    if tagged {
+        H::hash_tagged_null(node)
+    } else {
+        H::hash_nodes(node, node)
+    }
+}
+
+/// The Merkle Tree
+/// Contains the tree itself as a vector of vector of hashes
+/// It is built from the bottom to the root, first vector is the leaves, the last the root
+/// Generic over the `Hasher` used to compute leaf and node hashes, defaulting to SHA3-256
+/// so existing callers keep working unchanged.
+pub struct MerkleTree<H: Hasher = Sha3_256Hasher> {
+    tree: Vec<Vec<H::Hash>>,
+    /// Whether leaves/nodes are hashed with the domain-separation tags from
+    /// `new_tagged`. Stored so `add`, `generate_proof` and `verify` stay
+    /// consistent with however the tree was built.
+    tagged: bool,
+}
+
+impl<H: Hasher> MerkleTree<H> {
+    /// data: a vector of an array of bytes to build the tree. Each element of the vector is a leaf to hash
+    ///
+    /// Uses the legacy, untagged hashing (`H(value)` for leaves, `H(left ||
+    /// right)` for nodes) for backwards compatibility. Prefer `new_tagged`
+    /// for new trees: it closes a second-preimage hole where a node's
+    /// preimage can be replayed as a leaf.
+    pub fn new(data: &Vec<&[u8]>) -> MerkleTree<H> {
+        Self::build_tree(data, false)
+    }
+
+    /// Like `new`, but hashes leaves as `H(0x00 || value)` and nodes as
+    /// `H(0x01 || left || right)`. This produces a different root than
+    /// `new` over the same data, so switching an existing deployment is a
+    /// deliberate migration, not a drop-in upgrade.
+    pub fn new_tagged(data: &Vec<&[u8]>) -> MerkleTree<H> {
+        Self::build_tree(data, true)
+    }
+
+    fn build_tree(data: &Vec<&[u8]>, tagged: bool) -> MerkleTree<H> {
+        let mut merkle = MerkleTree {
+            tree: Vec::new(),
+            tagged,
+        };
+        if data.is_empty() {
+            return merkle;
+        }
+        // push hashes of the input
+        let leaves: Vec<H::Hash> = data
+            .iter()
+            .map(|value| leaf_hash::<H>(merkle.tagged, value))
+            .collect();
+
+        merkle.tree.push(leaves);
+        merkle.build();
+        merkle
+    }
+
+    /// private function to build a tree bottom up from the leaves
+    fn build(&mut self) {
+        while let Some(previous_level) = self.tree.last() {
+            if previous_level.len() == 1 {
+                // root achieved
+                break;
+            }
+            let mut new_level: Vec<H::Hash> = Vec::new();
+            for (i, hash_i) in previous_level.iter().enumerate() {
+                // take hashes by two => take by even indexes
+                if i % 2 != 0 {
+                    continue;
+                }
+
+                let concatenated_hash: H::Hash = match previous_level.get(i + 1) {
+                    Some(sibling_hash) => node_hash::<H>(self.tagged, hash_i, sibling_hash),
+                    None => null_hash::<H>(self.tagged, hash_i), // no sibling: null tag when tagged, self-concatenation otherwise
+                };
+                new_level.push(concatenated_hash);
+            }
+            self.tree.push(new_level);
+        }
+    }
+
+    /// value: elemento to search if it is in a leaf
+    /// then return only necesary hashes to calculate the root
+    /// Returns the Proof or an Error
+    pub fn generate_proof(&self, value: &[u8]) -> Result<Proof<H>, MerkleError> {
+        let element_index = match self.search_index(value) {
+            Some(i) => i,
+            None => return Err(MerkleError::NonExistingElement),
+        };
+        let mut actual_index = element_index;
+        let mut proofs = Vec::new();
+        for level in &self.tree {
+            if level.len() == 1 {
+                // root achieved
+                break;
+            }
+            // if even index, should look for right siblign
+            // if odd, look for left sibling
+            let sibling_index = if actual_index % 2 == 0 {
+                actual_index + 1
+            } else {
+                actual_index - 1
+            };
+
+            match level.get(sibling_index) {
+                Some(hash) => proofs.push(*hash),
+                None => proofs.push(*level.get(actual_index).unwrap()),
+            };
+
+            // the reason for actual_index is divided by two is because in the parent level
+            // it has a half of hashes. Then when divided by two it gets the floor of the division
+            // reaching the correct index
+            actual_index /= 2;
+        }
+
+        let leaf_count = self.tree.first().map_or(0, Vec::len);
+        Ok(Proof {
+            path: MerklePath(proofs),
+            index: element_index,
+            root: MerkleRoot::new(*self.get_root().unwrap(), self.tagged, leaf_count),
+        })
+    }
+
+    /// values: several elements to prove membership of in one shot.
+    /// Walks the tree level by level and only emits the sibling hashes that
+    /// can't be derived from another proven leaf or an already-derived node,
+    /// so proving many leaves costs far less than one `generate_proof` per leaf.
+    pub fn generate_multiproof(&self, values: &[&[u8]]) -> Result<MultiProof<H>, MerkleError> {
+        if values.is_empty() {
+            return Err(MerkleError::EmptyInput);
+        }
+
+        let mut indices = Vec::with_capacity(values.len());
+        for value in values {
+            match self.search_index(value) {
+                Some(i) => indices.push(i),
+                None => return Err(MerkleError::NonExistingElement),
+            }
+        }
+        indices.sort_unstable();
+        indices.dedup();
+
+        let leaf_count = self.tree.first().map_or(0, Vec::len);
+        let mut known: BTreeSet<usize> = indices.iter().copied().collect();
+        let mut hashes = Vec::new();
+
+        for level in &self.tree {
+            if level.len() == 1 {
+                break;
+            }
+
+            let pair_indices: BTreeSet<usize> = known.iter().map(|index| index / 2).collect();
+            let mut next_known = BTreeSet::new();
+
+            for pair_index in pair_indices {
+                let left_index = pair_index * 2;
+                let right_index = pair_index * 2 + 1;
+                let right_exists = right_index < level.len();
+
+                if !known.contains(&left_index) {
+                    hashes.push(level[left_index]);
+                }
+                if right_exists && !known.contains(&right_index) {
+                    hashes.push(level[right_index]);
+                }
+                next_known.insert(pair_index);
+            }
+
+            known = next_known;
+        }
+
+        Ok(MultiProof {
+            indices,
+            hashes,
+            leaf_count,
+            root: MerkleRoot::new(*self.get_root().unwrap(), self.tagged, leaf_count),
+        })
+    }
+
+    /// given a value, looks for and index in the leaf vector of the tree. Returns its index
+    fn search_index(&self, value: &[u8]) -> Option<usize> {
+        let value_hash = leaf_hash::<H>(self.tagged, value);
+        // the first element of the tree are the leaves
+        // then if not empty tree, search the position of the value hashed
+        if let Some(leaves) = self.tree.first() {
+            leaves.iter().position(|x| *x == value_hash)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn get_root(&self) -> Option<&H::Hash> {
+        if let Some(root_level) = self.tree.last() {
+            root_level.first()
+        } else {
+            None
+        }
+    }
+
+    /// Thin compatibility shim so callers that already have a tree don't
+    /// need to build a `MerkleRoot` by hand. A light client with just
+    /// `proof.root` and `proof.path` can instead call `proof.root.check`
+    /// directly, with zero access to the original tree.
+    pub fn verify(&self, proof: &Proof<H>, value: &[u8]) -> bool {
+        proof.root.check(&proof.path, value, proof.index)
+    }
+
+    /// value: new element to be added to the tree. It has to be an array of bytes
+    pub fn add(&mut self, value: &[u8]) {
+        let new_leaf = leaf_hash::<H>(self.tagged, value);
+        let leaves = self.get_mut_leaves();
+        leaves.push(new_leaf);
+
+        // tree with only one element, the root
+        if leaves.len() == 1 {
+            return;
+        }
+
+        let mut actual_index = leaves.len() - 1;
+
+        // iterate once per level in the tree to create or update the hashes
+        for i in 0..self.tree.len() - 1 {
+            let current_level = &self.tree[i];
+
+            // determine which side to look for the hash
+            let sibling_index = if actual_index % 2 == 0 {
+                actual_index + 1
+            } else {
+                actual_index - 1
+            };
+
+            let self_hash = *current_level.get(actual_index).unwrap();
+            let sibling_hash = current_level.get(sibling_index).copied();
+
+            let new_hash = match sibling_hash {
+                Some(sibling_hash) => {
+                    if actual_index % 2 == 0 {
+                        node_hash::<H>(self.tagged, &self_hash, &sibling_hash)
+                    } else {
+                        node_hash::<H>(self.tagged, &sibling_hash, &self_hash)
+                    }
+                }
+                None => null_hash::<H>(self.tagged, &self_hash),
+            };
+
+            actual_index /= 2;
+
+            // now check if element is present, then update the hash (only occur when the same hash is used to create a new one)
+            // if not present, push the new hash
+            match self.tree[i + 1].get(actual_index) {
+                Some(_) => self.tree[i + 1][actual_index] = new_hash,
+                None => self.tree[i + 1].push(new_hash),
+            }
+        }
+
+        // this is for when the tree raise one level. The previous root level now has two elements instead of one
+        // then we need to create a new level with the hash of the two elements concatenated
+        if let Some(last_level) = self.tree.last() {
+            if last_level.len() == 2 {
+                let new_root = node_hash::<H>(self.tagged, &last_level[0], &last_level[1]);
+                self.tree.push(vec![new_root]);
+            }
+        }
+    }
+
+    /// index: position of the leaf to replace
+    /// new_value: new array of bytes to hash into that leaf
+    ///
+    /// Rehashes only the O(log n) nodes on `index`'s path up to the root,
+    /// reusing the untouched sibling hashes already cached in `self.tree`
+    /// instead of rebuilding the whole tree.
+    pub fn update(&mut self, index: usize, new_value: &[u8]) -> Result<(), MerkleError> {
+        let leaf_count = self.tree.first().map_or(0, Vec::len);
+        if index >= leaf_count {
+            return Err(MerkleError::OutOfBounds);
+        }
+
+        self.tree[0][index] = leaf_hash::<H>(self.tagged, new_value);
+
+        let mut actual_index = index;
+        for i in 0..self.tree.len() - 1 {
+            let current_level = &self.tree[i];
+
+            let sibling_index = if actual_index % 2 == 0 {
+                actual_index + 1
+            } else {
+                actual_index - 1
+            };
+
+            let self_hash = current_level[actual_index];
+            let sibling_hash = current_level.get(sibling_index).copied();
+
+            let new_hash = match sibling_hash {
+                Some(sibling_hash) => {
+                    if actual_index % 2 == 0 {
+                        node_hash::<H>(self.tagged, &self_hash, &sibling_hash)
+                    } else {
+                        node_hash::<H>(self.tagged, &sibling_hash, &self_hash)
+                    }
+                }
+                None => null_hash::<H>(self.tagged, &self_hash),
+            };
+
+            actual_index /= 2;
+            self.tree[i + 1][actual_index] = new_hash;
+        }
+
+        Ok(())
+    }
+
+    fn get_mut_leaves(&mut self) -> &mut Vec<H::Hash> {
+        if self.tree.first_mut().is_none() {
+            let leaves = vec![];
+            self.tree.push(leaves);
+        }
+        // I ensured it's not empty
+        self.tree.first_mut().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type TestTree = MerkleTree;
+
+    #[test]
+    fn power_of_2_data_input() {
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a", b"merkleTree"];
+
+        // calculate hashes with the library
+        let leaf_hash = vec![
+            Sha3_256Hasher::hash_leaf(data[0]),
+            Sha3_256Hasher::hash_leaf(data[1]),
+            Sha3_256Hasher::hash_leaf(data[2]),
+            Sha3_256Hasher::hash_leaf(data[3]),
+        ];
+        let first_level = vec![
+            Sha3_256Hasher::hash_nodes(&leaf_hash[0], &leaf_hash[1]),
+            Sha3_256Hasher::hash_nodes(&leaf_hash[2], &leaf_hash[3]),
+        ];
+        let root = vec![Sha3_256Hasher::hash_nodes(&first_level[0], &first_level[1])];
+
+        let merkle: TestTree = MerkleTree::new(&data);
+
+        // compare merkle tree has the same hashes an lengthes
+        assert_eq!(leaf_hash, merkle.tree[0]);
+        assert_eq!(first_level, merkle.tree[1]);
+        assert_eq!(root, merkle.tree[2]);
+    }
+
+    #[test]
+    fn not_power_of_2_data_input() {
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a", b"merkle", b"tree"];
+        let leaf_hash = vec![
+            Sha3_256Hasher::hash_leaf(data[0]),
+            Sha3_256Hasher::hash_leaf(data[1]),
+            Sha3_256Hasher::hash_leaf(data[2]),
+            Sha3_256Hasher::hash_leaf(data[3]),
+            Sha3_256Hasher::hash_leaf(data[4]),
+        ];
+        let first_level = vec![
+            Sha3_256Hasher::hash_nodes(&leaf_hash[0], &leaf_hash[1]),
+            Sha3_256Hasher::hash_nodes(&leaf_hash[2], &leaf_hash[3]),
+            Sha3_256Hasher::hash_nodes(&leaf_hash[4], &leaf_hash[4]),
+        ];
+        let second_level = vec![
+            Sha3_256Hasher::hash_nodes(&first_level[0], &first_level[1]),
+            Sha3_256Hasher::hash_nodes(&first_level[2], &first_level[2]),
+        ];
+        let root = vec![Sha3_256Hasher::hash_nodes(&second_level[0], &second_level[1])];
+
+        let merkle: TestTree = MerkleTree::new(&data);
+
+        assert_eq!(leaf_hash, merkle.tree[0]);
+        assert_eq!(first_level, merkle.tree[1]);
+        assert_eq!(second_level, merkle.tree[2]);
+        assert_eq!(root, merkle.tree[3]);
+    }
+
+    #[test]
+    fn generate_proof_easy_path() {
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a", b"merkleTree"];
+
+        // calculate hashes with the library
+        let leaf_hash = [
+            Sha3_256Hasher::hash_leaf(data[0]),
+            Sha3_256Hasher::hash_leaf(data[1]),
+            Sha3_256Hasher::hash_leaf(data[2]),
+            Sha3_256Hasher::hash_leaf(data[3]),
+        ];
+        let first_level = [
+            Sha3_256Hasher::hash_nodes(&leaf_hash[0], &leaf_hash[1]),
+            Sha3_256Hasher::hash_nodes(&leaf_hash[2], &leaf_hash[3]),
+        ];
+
+        let merkle: TestTree = MerkleTree::new(&data);
+
+        let proof = merkle.generate_proof(b"is").unwrap();
+        assert_eq!(proof.path[0], leaf_hash[0]);
+        assert_eq!(proof.path[1], first_level[1]);
+        assert_eq!(proof.path.len(), 2);
+        assert_eq!(proof.root.hash(), merkle.tree.last().unwrap()[0]);
+        assert_eq!(proof.index, 1);
+    }
+
+    #[test]
+    fn generate_proof_easy_path_start_on_right() {
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a", b"merkleTree"];
+
+        // calculate hashes with the library
+        let leaf_hash = [
+            Sha3_256Hasher::hash_leaf(data[0]),
+            Sha3_256Hasher::hash_leaf(data[1]),
+            Sha3_256Hasher::hash_leaf(data[2]),
+            Sha3_256Hasher::hash_leaf(data[3]),
+        ];
+        let first_level = [
+            Sha3_256Hasher::hash_nodes(&leaf_hash[0], &leaf_hash[1]),
+            Sha3_256Hasher::hash_nodes(&leaf_hash[2], &leaf_hash[3]),
+        ];
+
+        let merkle: TestTree = MerkleTree::new(&data);
+
+        let proof = merkle.generate_proof(b"a").unwrap();
+        assert_eq!(proof.path[0], leaf_hash[3]);
+        assert_eq!(proof.path[1], first_level[0]);
+        assert_eq!(proof.path.len(), 2);
+        assert_eq!(proof.root.hash(), merkle.tree.last().unwrap()[0]);
+        assert_eq!(proof.index, 2);
+    }
+
+    #[test]
+    fn generate_proof_on_five_entries() {
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a", b"merkle", b"tree"];
+        let leaf_hash = [
+            Sha3_256Hasher::hash_leaf(data[0]),
+            Sha3_256Hasher::hash_leaf(data[1]),
+            Sha3_256Hasher::hash_leaf(data[2]),
+            Sha3_256Hasher::hash_leaf(data[3]),
+            Sha3_256Hasher::hash_leaf(data[4]),
+        ];
+        let first_level = [
+            Sha3_256Hasher::hash_nodes(&leaf_hash[0], &leaf_hash[1]),
+            Sha3_256Hasher::hash_nodes(&leaf_hash[2], &leaf_hash[3]),
+            Sha3_256Hasher::hash_nodes(&leaf_hash[4], &leaf_hash[4]),
+        ];
+        let second_level = [
+            Sha3_256Hasher::hash_nodes(&first_level[0], &first_level[1]),
+            Sha3_256Hasher::hash_nodes(&first_level[2], &first_level[2]),
+        ];
+
+        let merkle: TestTree = MerkleTree::new(&data);
+
+        let proof = merkle.generate_proof(b"tree").unwrap();
+        assert_eq!(proof.path[0], leaf_hash[4]);
+        assert_eq!(proof.path[1], first_level[2]);
+        assert_eq!(proof.path[2], second_level[0]);
+        assert_eq!(proof.path.len(), 3);
+        assert_eq!(proof.root.hash(), merkle.tree.last().unwrap()[0]);
+        assert_eq!(proof.index, 4);
+    }
+
+    #[test]
+    fn error_proof_on_notexisting_element() {
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a", b"merkle", b"tree"];
+        let merkle: TestTree = MerkleTree::new(&data);
+        assert!(merkle.generate_proof(b"non_existing").is_err());
+    }
+
+    #[test]
+    fn error_proof_on_empty_tree() {
+        let data: Vec<&[u8]> = vec![];
+        let merkle: TestTree = MerkleTree::new(&data);
+        assert!(merkle.generate_proof(b"non_existing").is_err());
+    }
+
+    #[test]
+    fn happy_verify() {
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a", b"merkleTree"];
+        let merkle: TestTree = MerkleTree::new(&data);
+        let proof = merkle.generate_proof(b"is").unwrap();
+        assert!(merkle.verify(&proof, b"is"));
+    }
+
+    #[test]
+    fn bad_verify_different_proof_for_element() {
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a", b"merkleTree"];
+        let merkle: TestTree = MerkleTree::new(&data);
+        let proof = merkle.generate_proof(b"is").unwrap();
+        assert!(!merkle.verify(&proof, b"a"));
+    }
+
+    #[test]
+    fn bad_verify_wrong_root() {
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a", b"merkleTree"];
+        let merkle: TestTree = MerkleTree::new(&data);
+        let proof = merkle.generate_proof(b"is").unwrap();
+        let bad_root = MerkleRoot::<Sha3_256Hasher>::new([0_u8; 32], false, 4);
+        assert!(!bad_root.check(&proof.path, b"a", proof.index));
+    }
+
+    #[test]
+    fn verify_needs_no_access_to_the_original_tree() {
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a", b"merkleTree"];
+        let merkle: TestTree = MerkleTree::new(&data);
+        let proof = merkle.generate_proof(b"is").unwrap();
+
+        // a light client only has `proof.root` and `proof.path`, never `merkle`
+        assert!(proof.root.check(&proof.path, b"is", proof.index));
+    }
+
+    #[test]
+    fn add_correct_for_three_elements() {
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a"];
+
+        let mut merkle: TestTree = MerkleTree::new(&data);
+        merkle.add(b"merkleTree");
+
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a", b"merkleTree"];
+
+        // calculate hashes with the library
+        let leaf_hash = vec![
+            Sha3_256Hasher::hash_leaf(data[0]),
+            Sha3_256Hasher::hash_leaf(data[1]),
+            Sha3_256Hasher::hash_leaf(data[2]),
+            Sha3_256Hasher::hash_leaf(data[3]),
+        ];
+        let first_level = vec![
+            Sha3_256Hasher::hash_nodes(&leaf_hash[0], &leaf_hash[1]),
+            Sha3_256Hasher::hash_nodes(&leaf_hash[2], &leaf_hash[3]),
+        ];
+        let root = vec![Sha3_256Hasher::hash_nodes(&first_level[0], &first_level[1])];
+
+        assert_eq!(leaf_hash, merkle.tree[0]);
+        assert_eq!(first_level, merkle.tree[1]);
+        assert_eq!(root, merkle.tree[2]);
+        assert_eq!(3, merkle.tree.len());
+    }
+
+    #[test]
+    fn add_correct_for_four_elements() {
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a", b"merkle"];
+
+        let mut merkle: TestTree = MerkleTree::new(&data);
+        merkle.add(b"tree");
+
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a", b"merkle", b"tree"];
+        let leaf_hash = vec![
+            Sha3_256Hasher::hash_leaf(data[0]),
+            Sha3_256Hasher::hash_leaf(data[1]),
+            Sha3_256Hasher::hash_leaf(data[2]),
+            Sha3_256Hasher::hash_leaf(data[3]),
+            Sha3_256Hasher::hash_leaf(data[4]),
+        ];
+        let first_level = vec![
+            Sha3_256Hasher::hash_nodes(&leaf_hash[0], &leaf_hash[1]),
+            Sha3_256Hasher::hash_nodes(&leaf_hash[2], &leaf_hash[3]),
+            Sha3_256Hasher::hash_nodes(&leaf_hash[4], &leaf_hash[4]),
+        ];
+        let second_level = vec![
+            Sha3_256Hasher::hash_nodes(&first_level[0], &first_level[1]),
+            Sha3_256Hasher::hash_nodes(&first_level[2], &first_level[2]),
+        ];
+        let root = vec![Sha3_256Hasher::hash_nodes(&second_level[0], &second_level[1])];
+
+        assert_eq!(leaf_hash, merkle.tree[0]);
+        assert_eq!(first_level, merkle.tree[1]);
+        assert_eq!(second_level, merkle.tree[2]);
+        assert_eq!(root, merkle.tree[3]);
+        assert_eq!(4, merkle.tree.len());
+    }
+
+    #[test]
+    fn update_matches_a_freshly_built_tree_over_the_mutated_data() {
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a", b"merkleTree"];
+        let mut merkle: TestTree = MerkleTree::new(&data);
+        merkle.update(1, b"was").unwrap();
+
+        let mutated: Vec<&[u8]> = vec![b"this", b"was", b"a", b"merkleTree"];
+        let rebuilt: TestTree = MerkleTree::new(&mutated);
+
+        assert_eq!(merkle.tree, rebuilt.tree);
+    }
+
+    #[test]
+    fn update_matches_a_freshly_built_tree_with_an_odd_number_of_leaves() {
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a"];
+        let mut merkle: TestTree = MerkleTree::new(&data);
+        merkle.update(2, b"tree").unwrap();
+
+        let mutated: Vec<&[u8]> = vec![b"this", b"is", b"tree"];
+        let rebuilt: TestTree = MerkleTree::new(&mutated);
+
+        assert_eq!(merkle.tree, rebuilt.tree);
+    }
+
+    #[test]
+    fn update_out_of_bounds_returns_an_error() {
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a"];
+        let mut merkle: TestTree = MerkleTree::new(&data);
+
+        assert!(matches!(
+            merkle.update(3, b"merkleTree"),
+            Err(MerkleError::OutOfBounds)
+        ));
+    }
+
+    #[test]
+    fn update_on_an_empty_tree_returns_an_error() {
+        let mut merkle: TestTree = MerkleTree::new(&vec![]);
+
+        assert!(matches!(
+            merkle.update(0, b"this"),
+            Err(MerkleError::OutOfBounds)
+        ));
+    }
+
+    #[test]
+    fn tagged_leaf_and_node_hash_differ_for_same_bytes() {
+        // without the domain tag an internal node's preimage could be
+        // replayed as a leaf; the tagged hashes must never collide.
+        let leaf = Sha3_256Hasher::hash_tagged_leaf(b"this");
+        let node = Sha3_256Hasher::hash_tagged_nodes(&leaf, &leaf);
+        assert_ne!(leaf, node);
+    }
+
+    #[test]
+    fn tagged_tree_produces_different_root_than_untagged() {
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a", b"merkleTree"];
+        let untagged: TestTree = MerkleTree::new(&data);
+        let tagged: TestTree = MerkleTree::new_tagged(&data);
+        assert_ne!(
+            untagged.tree.last().unwrap()[0],
+            tagged.tree.last().unwrap()[0]
+        );
+    }
+
+    #[test]
+    fn tagged_tree_proof_round_trips() {
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a", b"merkle", b"tree"];
+        let merkle: TestTree = MerkleTree::new_tagged(&data);
+        let proof = merkle.generate_proof(b"tree").unwrap();
+        assert!(merkle.verify(&proof, b"tree"));
+    }
+
+    #[test]
+    fn sha512_tree_proof_round_trips() {
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a", b"merkle", b"tree"];
+        let merkle: MerkleTree<Sha512Hasher> = MerkleTree::new_tagged(&data);
+        let proof = merkle.generate_proof(b"tree").unwrap();
+        assert!(merkle.verify(&proof, b"tree"));
+
+        let multiproof = merkle.generate_multiproof(&[b"is", b"tree"]).unwrap();
+        assert!(multiproof.verify(&[b"is", b"tree"]));
+    }
+
+    #[test]
+    fn generate_multiproof_rejects_an_empty_values_slice() {
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a", b"merkleTree"];
+        let merkle: TestTree = MerkleTree::new(&data);
+        assert!(matches!(
+            merkle.generate_multiproof(&[]),
+            Err(MerkleError::EmptyInput)
+        ));
+
+        let empty: TestTree = MerkleTree::new(&vec![]);
+        assert!(matches!(
+            empty.generate_multiproof(&[]),
+            Err(MerkleError::EmptyInput)
+        ));
+    }
+
+}
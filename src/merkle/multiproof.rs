@@ -0,0 +1,165 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::{leaf_hash, node_hash, null_hash, Hasher, MerkleRoot};
+
+/// Proof that several leaves are members of a tree, sharing any sibling
+/// hash that can be reconstructed from the other leaves being proven
+/// instead of paying for fully redundant single-leaf proofs.
+pub struct MultiProof<H: Hasher = super::Sha3_256Hasher> {
+    /// Sorted, deduplicated indices of the leaves this proof covers.
+    pub indices: Vec<usize>,
+    /// The sibling hashes that could not be derived from the proven leaves
+    /// themselves, in the order `verify` expects to consume them.
+    pub hashes: Vec<H::Hash>,
+    /// Number of leaves in the tree the proof was generated from, needed to
+    /// tell a genuinely missing sibling (the end of an odd level) apart from
+    /// one that was simply left out of `hashes`.
+    pub leaf_count: usize,
+    pub root: MerkleRoot<H>,
+}
+
+impl<H: Hasher> MultiProof<H> {
+    /// Verifies that `values[i]` is the leaf at `self.indices[i]` for every
+    /// `i`, rebuilding each level by pairing known nodes and consuming a
+    /// supplied sibling only when the pair-partner is absent.
+    pub fn verify(&self, values: &[&[u8]]) -> bool {
+        // A proof of zero leaves is invalid input, not a vacuous truth: the
+        // level-collapsing loop below has nothing to seed itself with and
+        // would never reach the root, so reject it up front instead of
+        // silently returning false for the wrong reason.
+        if values.is_empty() || values.len() != self.indices.len() {
+            return false;
+        }
+
+        let mut level: BTreeMap<usize, H::Hash> = BTreeMap::new();
+        for (&index, value) in self.indices.iter().zip(values.iter()) {
+            if level
+                .insert(index, leaf_hash::<H>(self.root.tagged(), value))
+                .is_some()
+            {
+                return false; // duplicate index
+            }
+        }
+
+        let mut remaining = self.hashes.iter();
+        let mut level_size = self.leaf_count;
+
+        loop {
+            if level_size <= 1 {
+                return level.get(&0).copied() == Some(self.root.hash());
+            }
+
+            let mut next_level = BTreeMap::new();
+            let pair_indices: BTreeSet<usize> = level.keys().map(|index| index / 2).collect();
+
+            for pair_index in pair_indices {
+                let left_index = pair_index * 2;
+                let right_index = pair_index * 2 + 1;
+                let right_exists = right_index < level_size;
+
+                let left = match level.get(&left_index) {
+                    Some(hash) => *hash,
+                    None => match remaining.next() {
+                        Some(hash) => *hash,
+                        None => return false,
+                    },
+                };
+
+                let parent = if right_exists {
+                    let right = match level.get(&right_index) {
+                        Some(hash) => *hash,
+                        None => match remaining.next() {
+                            Some(hash) => *hash,
+                            None => return false,
+                        },
+                    };
+                    node_hash::<H>(self.root.tagged(), &left, &right)
+                } else {
+                    null_hash::<H>(self.root.tagged(), &left)
+                };
+
+                next_level.insert(pair_index, parent);
+            }
+
+            level = next_level;
+            level_size = (level_size + 1) / 2;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{MerkleRoot, MerkleTree, Sha3_256Hasher};
+    use super::*;
+
+    type TestTree = MerkleTree;
+
+    #[test]
+    fn verify_rejects_a_proof_of_zero_leaves() {
+        let proof = MultiProof::<Sha3_256Hasher> {
+            indices: Vec::new(),
+            hashes: Vec::new(),
+            leaf_count: 4,
+            root: MerkleRoot::new([0_u8; 32], false, 4),
+        };
+
+        assert!(!proof.verify(&[]));
+    }
+
+    #[test]
+    fn accepts_same_leaves_as_union_of_single_proofs() {
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a", b"merkleTree"];
+        let merkle: TestTree = MerkleTree::new(&data);
+
+        for value in &[b"is".as_slice(), b"merkleTree".as_slice()] {
+            let proof = merkle.generate_proof(value).unwrap();
+            assert!(merkle.verify(&proof, value));
+        }
+
+        let multiproof = merkle
+            .generate_multiproof(&[b"is", b"merkleTree"])
+            .unwrap();
+        assert!(multiproof.verify(&[b"is", b"merkleTree"]));
+    }
+
+    #[test]
+    fn on_odd_entries_handles_the_unpaired_leaf() {
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a", b"merkle", b"tree"];
+        let merkle: TestTree = MerkleTree::new(&data);
+
+        let multiproof = merkle.generate_multiproof(&[b"this", b"tree"]).unwrap();
+        assert!(multiproof.verify(&[b"this", b"tree"]));
+    }
+
+    #[test]
+    fn rejects_wrong_leaf_value() {
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a", b"merkleTree"];
+        let merkle: TestTree = MerkleTree::new(&data);
+
+        let multiproof = merkle
+            .generate_multiproof(&[b"is", b"merkleTree"])
+            .unwrap();
+        assert!(!multiproof.verify(&[b"is", b"not-merkleTree"]));
+    }
+
+    #[test]
+    fn shares_hashes_across_leaves() {
+        // proving both leaves of the same pair needs no sibling hash at the
+        // leaf level, since each leaf is the other's sibling; only the hash
+        // of the other pair is needed one level up.
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a", b"merkleTree"];
+        let merkle: TestTree = MerkleTree::new(&data);
+
+        let multiproof = merkle.generate_multiproof(&[b"this", b"is"]).unwrap();
+        assert_eq!(multiproof.hashes.len(), 1);
+    }
+
+    #[test]
+    fn on_non_existing_element() {
+        let data: Vec<&[u8]> = vec![b"this", b"is", b"a", b"merkleTree"];
+        let merkle: TestTree = MerkleTree::new(&data);
+        assert!(merkle
+            .generate_multiproof(&[b"is", b"non_existing"])
+            .is_err());
+    }
+}
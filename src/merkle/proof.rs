@@ -0,0 +1,89 @@
+use std::ops::Deref;
+
+use super::{leaf_hash, node_hash, null_hash, Hasher};
+
+/// A Merkle root together with the domain-separation mode it was computed
+/// with. Carries everything `check` needs to verify membership, so a light
+/// client can hold just this (plus a `MerklePath`) without ever touching the
+/// original `MerkleTree`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MerkleRoot<H: Hasher> {
+    hash: H::Hash,
+    tagged: bool,
+    /// Number of leaves in the tree this root was computed from, needed to
+    /// tell a level's genuinely missing sibling (the tail of an odd level)
+    /// apart from a real one, the same way `MultiProof` tracks `leaf_count`.
+    leaf_count: usize,
+}
+
+impl<H: Hasher> MerkleRoot<H> {
+    pub fn new(hash: H::Hash, tagged: bool, leaf_count: usize) -> Self {
+        MerkleRoot {
+            hash,
+            tagged,
+            leaf_count,
+        }
+    }
+
+    pub fn hash(&self) -> H::Hash {
+        self.hash
+    }
+
+    /// Whether this root was computed with the domain-separation tags from `MerkleTree::new_tagged`.
+    pub(crate) fn tagged(&self) -> bool {
+        self.tagged
+    }
+
+    /// Recomputes the root from `leaf` and `path`, walking upward using the
+    /// same odd/even sibling pairing `MerkleTree` uses to build itself, then
+    /// compares it against this root.
+    pub fn check(&self, path: &MerklePath<H>, leaf: &[u8], index: usize) -> bool {
+        let mut actual_index = index;
+        let mut actual_hash = leaf_hash::<H>(self.tagged, leaf);
+        let mut level_size = self.leaf_count;
+
+        for sibling in &path.0 {
+            let sibling_index = if actual_index % 2 == 0 {
+                actual_index + 1
+            } else {
+                actual_index - 1
+            };
+            let has_sibling = sibling_index < level_size;
+
+            actual_hash = if !has_sibling {
+                null_hash::<H>(self.tagged, &actual_hash)
+            } else if actual_index % 2 == 0 {
+                node_hash::<H>(self.tagged, &actual_hash, sibling)
+            } else {
+                node_hash::<H>(self.tagged, sibling, &actual_hash)
+            };
+            actual_index /= 2;
+            level_size = level_size / 2 + level_size % 2;
+        }
+
+        actual_hash == self.hash
+    }
+}
+
+/// The sibling hashes of a Merkle proof, ordered from the leaf's sibling up to
+/// the level just below the root.
+#[derive(Debug, Clone)]
+pub struct MerklePath<H: Hasher>(pub Vec<H::Hash>);
+
+impl<H: Hasher> Deref for MerklePath<H> {
+    type Target = Vec<H::Hash>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Result type when generating proofs.
+/// path: the sibling hashes necessary to verify the proof
+/// index: is the index of the element in the leaf level
+/// root: root of the tree, carried alongside the hashing mode it was built with
+pub struct Proof<H: Hasher = super::Sha3_256Hasher> {
+    pub path: MerklePath<H>,
+    pub index: usize,
+    pub root: MerkleRoot<H>,
+}
@@ -0,0 +1,432 @@
+use std::collections::HashMap;
+
+use super::Hasher;
+
+/// A Merkle tree over the entire key space of `H::Hash` (one leaf slot per
+/// possible hash value), rather than over however many elements happen to
+/// have been inserted. Most of that space is implicitly empty, so storage
+/// grows with the number of insertions, not with the size of the key space
+/// (nodes superseded by a later insert are not removed): empty subtrees collapse to
+/// a single cached hash per height instead of being materialized, and a
+/// subtree with exactly one real occupant is stored as one `Node::Leaf`
+/// instead of a full chain of nodes down to the leaf level.
+///
+/// Hashing deliberately skips this crate's tagged-hashing mode, so the root
+/// stays byte-compatible with a naive, fully-expanded sparse Merkle tree
+/// over the same keys and values.
+pub struct SparseMerkleTree<H: Hasher = super::Sha3_256Hasher> {
+    /// Depth of the full tree: the bit-length of `H::Hash`.
+    depth: usize,
+    /// Hash of an empty subtree rooted at each height; `empty_hash[0]` is the empty leaf.
+    empty_hash: Vec<H::Hash>,
+    /// Real, non-empty nodes, content-addressed by their own hash.
+    nodes: HashMap<H::Hash, Node<H>>,
+    /// The raw value bytes stored for each key, keyed by the key's hash.
+    values: HashMap<H::Hash, Vec<u8>>,
+    root: H::Hash,
+}
+
+enum Node<H: Hasher> {
+    /// A subtree whose only occupant is `key`, with no further splits below it.
+    Leaf { key: H::Hash, value: H::Hash },
+    Internal { left: H::Hash, right: H::Hash },
+}
+
+// Hand-written instead of derived: `derive(Copy)` would add an `H: Copy`
+// bound even though `H` only ever appears through `H::Hash` (already `Copy`
+// per the `Hasher` trait bound), which would make `Node<H>` uncopyable for
+// any generic `H` that doesn't itself happen to implement `Copy`.
+impl<H: Hasher> Clone for Node<H> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<H: Hasher> Copy for Node<H> {}
+
+/// What occupies the leaf slot a `SparseProof` was built for.
+pub enum Terminal<H: Hasher> {
+    /// No key hashing to this slot has ever been inserted.
+    Empty,
+    /// A key occupies the collapsed subtree the queried key's path ends in.
+    /// If its hash matches the queried key, this is a membership proof;
+    /// otherwise the queried key's own slot is empty, `verify` treats it
+    /// like `Empty`, and the occupant is checked only through the siblings.
+    Occupied { key: H::Hash, value: H::Hash },
+}
+
+/// A proof that a key is or isn't present in a `SparseMerkleTree`, carrying
+/// everything needed to check that against a root with no access to the
+/// original tree.
+pub struct SparseProof<H: Hasher = super::Sha3_256Hasher> {
+    pub key: Vec<u8>,
+    /// Sibling hashes from the leaf level up to one below the root.
+    pub siblings: Vec<H::Hash>,
+    pub terminal: Terminal<H>,
+    /// Depth of the tree this proof was generated against, i.e. `self.siblings.len()`.
+    pub depth: usize,
+}
+
+impl<H: Hasher> SparseProof<H> {
+    /// Recomputes the root from this proof's terminal and siblings, walking
+    /// upward by the queried key's bit path the same way `MerkleRoot::check`
+    /// walks a normal proof by index, then compares it against `root`.
+    pub fn verify(&self, root: &H::Hash) -> bool {
+        if self.siblings.len() != self.depth {
+            return false;
+        }
+
+        let key_hash = H::hash_leaf(&self.key);
+        let empty_hash = empty_hash_chain::<H>(self.depth);
+
+        let mut hash = match &self.terminal {
+            Terminal::Empty => empty_hash[0],
+            Terminal::Occupied { key, value } if *key == key_hash => *value,
+            // a different key occupies this slot: from the queried key's own
+            // point of view the slot is empty, the occupant only shows up
+            // through whichever sibling captures its folded subtree.
+            Terminal::Occupied { .. } => empty_hash[0],
+        };
+
+        for height in 0..self.depth {
+            let level_idx = self.depth - height - 1;
+            hash = if bit::<H>(&key_hash, level_idx) {
+                H::hash_nodes(&self.siblings[height], &hash)
+            } else {
+                H::hash_nodes(&hash, &self.siblings[height])
+            };
+        }
+
+        hash == *root
+    }
+}
+
+/// The hash of an empty subtree at each height, from the empty leaf up to the full tree.
+fn empty_hash_chain<H: Hasher>(depth: usize) -> Vec<H::Hash> {
+    let mut empty_hash = Vec::with_capacity(depth + 1);
+    empty_hash.push(H::hash_leaf(&[]));
+    for height in 0..depth {
+        let previous = empty_hash[height];
+        empty_hash.push(H::hash_nodes(&previous, &previous));
+    }
+    empty_hash
+}
+
+/// The bit of `hash` at `index`, counting from the most significant bit of the first byte.
+fn bit<H: Hasher>(hash: &H::Hash, index: usize) -> bool {
+    let byte = hash.as_ref()[index / 8];
+    (byte >> (7 - index % 8)) & 1 == 1
+}
+
+impl<H: Hasher> SparseMerkleTree<H> {
+    /// Builds an empty tree over the full `H::Hash` key space.
+    pub fn new() -> Self {
+        let depth = std::mem::size_of::<H::Hash>() * 8;
+        let empty_hash = empty_hash_chain::<H>(depth);
+        let root = empty_hash[depth];
+
+        SparseMerkleTree {
+            depth,
+            empty_hash,
+            nodes: HashMap::new(),
+            values: HashMap::new(),
+            root,
+        }
+    }
+
+    /// Bit-length of `H::Hash`, and therefore the depth of the full tree.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn root(&self) -> H::Hash {
+        self.root
+    }
+
+    /// Inserts, or overwrites, the value stored for `key`.
+    pub fn insert(&mut self, key: &[u8], value: &[u8]) {
+        let key_hash = H::hash_leaf(key);
+        let value_hash = H::hash_leaf(value);
+        self.values.insert(key_hash, value.to_vec());
+        self.root = self.insert_rec(self.root, self.depth, &key_hash, value_hash);
+    }
+
+    fn insert_rec(
+        &mut self,
+        node_hash: H::Hash,
+        height: usize,
+        key_hash: &H::Hash,
+        value_hash: H::Hash,
+    ) -> H::Hash {
+        if height == 0 {
+            return value_hash;
+        }
+
+        if node_hash == self.empty_hash[height] {
+            let folded = self.fold_leaf(key_hash, value_hash, height);
+            self.nodes.insert(
+                folded,
+                Node::Leaf {
+                    key: *key_hash,
+                    value: value_hash,
+                },
+            );
+            return folded;
+        }
+
+        let idx = self.depth - height;
+        let node = *self
+            .nodes
+            .get(&node_hash)
+            .expect("a non-empty node hash must have a stored node");
+
+        let (left, right) = match node {
+            Node::Internal { left, right } => (left, right),
+            Node::Leaf {
+                key: existing_key,
+                value: existing_value,
+            } => {
+                if existing_key == *key_hash {
+                    let folded = self.fold_leaf(key_hash, value_hash, height);
+                    self.nodes.insert(
+                        folded,
+                        Node::Leaf {
+                            key: existing_key,
+                            value: value_hash,
+                        },
+                    );
+                    return folded;
+                }
+
+                // Two different keys now share this subtree: push the
+                // existing occupant down one level and let the usual
+                // child recursion (below) split it from the new key.
+                let pushed_down = self.fold_leaf(&existing_key, existing_value, height - 1);
+                self.nodes.insert(
+                    pushed_down,
+                    Node::Leaf {
+                        key: existing_key,
+                        value: existing_value,
+                    },
+                );
+                if bit::<H>(&existing_key, idx) {
+                    (self.empty_hash[height - 1], pushed_down)
+                } else {
+                    (pushed_down, self.empty_hash[height - 1])
+                }
+            }
+        };
+
+        let (left, right) = if bit::<H>(key_hash, idx) {
+            (left, self.insert_rec(right, height - 1, key_hash, value_hash))
+        } else {
+            (self.insert_rec(left, height - 1, key_hash, value_hash), right)
+        };
+
+        let combined = H::hash_nodes(&left, &right);
+        self.nodes.insert(combined, Node::Internal { left, right });
+        combined
+    }
+
+    /// The value stored for `key`, if any.
+    pub fn get(&self, key: &[u8]) -> Option<&[u8]> {
+        let key_hash = H::hash_leaf(key);
+        self.values.get(&key_hash).map(Vec::as_slice)
+    }
+
+    /// Proves membership or non-membership of `key` against the current root.
+    pub fn prove(&self, key: &[u8]) -> SparseProof<H> {
+        let key_hash = H::hash_leaf(key);
+        let (terminal, siblings) = self.prove_rec(self.root, self.depth, &key_hash);
+        SparseProof {
+            key: key.to_vec(),
+            siblings,
+            terminal,
+            depth: self.depth,
+        }
+    }
+
+    /// Returns the terminal state for `key_hash` together with the sibling
+    /// hashes from the leaf level up to (but not including) `node_hash`,
+    /// collected bottom-up via the recursion unwinding.
+    fn prove_rec(
+        &self,
+        node_hash: H::Hash,
+        height: usize,
+        key_hash: &H::Hash,
+    ) -> (Terminal<H>, Vec<H::Hash>) {
+        if height == 0 {
+            let terminal = match self.values.get(key_hash) {
+                Some(raw) => Terminal::Occupied {
+                    key: *key_hash,
+                    value: H::hash_leaf(raw),
+                },
+                None => Terminal::Empty,
+            };
+            return (terminal, Vec::new());
+        }
+
+        if node_hash == self.empty_hash[height] {
+            let siblings = (0..height).map(|h| self.empty_hash[h]).collect();
+            return (Terminal::Empty, siblings);
+        }
+
+        match *self
+            .nodes
+            .get(&node_hash)
+            .expect("a non-empty node hash must have a stored node")
+        {
+            Node::Internal { left, right } => {
+                let idx = self.depth - height;
+                let (chosen, sibling) = if bit::<H>(key_hash, idx) {
+                    (right, left)
+                } else {
+                    (left, right)
+                };
+                let (terminal, mut siblings) = self.prove_rec(chosen, height - 1, key_hash);
+                siblings.push(sibling);
+                (terminal, siblings)
+            }
+            Node::Leaf {
+                key: existing_key,
+                value: existing_value,
+            } => {
+                let terminal = Terminal::Occupied {
+                    key: existing_key,
+                    value: existing_value,
+                };
+
+                if existing_key == *key_hash {
+                    let siblings = (0..height).map(|h| self.empty_hash[h]).collect();
+                    return (terminal, siblings);
+                }
+
+                let divergence_height = (1..=height).rev().find(|&h| {
+                    let level_idx = self.depth - h;
+                    bit::<H>(&existing_key, level_idx) != bit::<H>(key_hash, level_idx)
+                });
+
+                let mut siblings: Vec<H::Hash> = (1..=height)
+                    .rev()
+                    .map(|h| match divergence_height {
+                        Some(divergence) if divergence == h => {
+                            self.fold_leaf(&existing_key, existing_value, h - 1)
+                        }
+                        _ => self.empty_hash[h - 1],
+                    })
+                    .collect();
+                siblings.reverse();
+
+                (terminal, siblings)
+            }
+        }
+    }
+
+    /// Folds a single leaf's hash upward through `to_height` levels,
+    /// pairing it with the precomputed empty-subtree hash at each level on
+    /// whichever side `key_hash`'s own bit path leaves empty.
+    fn fold_leaf(&self, key_hash: &H::Hash, mut hash: H::Hash, to_height: usize) -> H::Hash {
+        for h in 0..to_height {
+            let level_idx = self.depth - (h + 1);
+            hash = if bit::<H>(key_hash, level_idx) {
+                H::hash_nodes(&self.empty_hash[h], &hash)
+            } else {
+                H::hash_nodes(&hash, &self.empty_hash[h])
+            };
+        }
+        hash
+    }
+}
+
+impl<H: Hasher> Default for SparseMerkleTree<H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Sha3_256Hasher;
+    use super::*;
+
+    #[test]
+    fn get_returns_the_inserted_value() {
+        let mut tree: SparseMerkleTree = SparseMerkleTree::new();
+        tree.insert(b"this", b"is");
+
+        assert_eq!(tree.get(b"this"), Some(b"is".as_slice()));
+    }
+
+    #[test]
+    fn get_returns_none_for_a_key_that_was_never_inserted() {
+        let mut tree: SparseMerkleTree = SparseMerkleTree::new();
+        tree.insert(b"this", b"is");
+
+        assert_eq!(tree.get(b"a"), None);
+    }
+
+    #[test]
+    fn insert_overwrites_the_value_for_an_existing_key() {
+        let mut tree: SparseMerkleTree = SparseMerkleTree::new();
+        tree.insert(b"this", b"is");
+        tree.insert(b"this", b"merkleTree");
+
+        assert_eq!(tree.get(b"this"), Some(b"merkleTree".as_slice()));
+    }
+
+    #[test]
+    fn root_changes_as_keys_are_inserted() {
+        let mut tree: SparseMerkleTree = SparseMerkleTree::new();
+        let empty_root = tree.root();
+
+        tree.insert(b"this", b"is");
+        assert_ne!(tree.root(), empty_root);
+
+        let root_after_one = tree.root();
+        tree.insert(b"a", b"merkleTree");
+        assert_ne!(tree.root(), root_after_one);
+    }
+
+    #[test]
+    fn prove_verifies_membership_for_an_inserted_key() {
+        let mut tree: SparseMerkleTree = SparseMerkleTree::new();
+        tree.insert(b"this", b"is");
+        tree.insert(b"a", b"merkleTree");
+
+        let proof = tree.prove(b"this");
+        assert!(proof.verify(&tree.root()));
+    }
+
+    #[test]
+    fn prove_verifies_non_membership_for_an_untouched_key_in_an_empty_tree() {
+        let tree: SparseMerkleTree = SparseMerkleTree::new();
+
+        let proof = tree.prove(b"this");
+        assert!(matches!(proof.terminal, Terminal::Empty));
+        assert!(proof.verify(&tree.root()));
+    }
+
+    #[test]
+    fn prove_verifies_non_membership_for_a_key_that_was_never_inserted() {
+        let mut tree: SparseMerkleTree = SparseMerkleTree::new();
+        tree.insert(b"this", b"is");
+        tree.insert(b"a", b"merkleTree");
+
+        let proof = tree.prove(b"not-inserted");
+        assert!(tree.get(b"not-inserted").is_none());
+        assert!(proof.verify(&tree.root()));
+    }
+
+    #[test]
+    fn prove_rejects_a_forged_value() {
+        let mut tree: SparseMerkleTree = SparseMerkleTree::new();
+        tree.insert(b"this", b"is");
+
+        let mut proof = tree.prove(b"this");
+        if let Terminal::Occupied { value, .. } = &mut proof.terminal {
+            *value = Sha3_256Hasher::hash_leaf(b"not-is");
+        }
+
+        assert!(!proof.verify(&tree.root()));
+    }
+}